@@ -0,0 +1,83 @@
+// Headless regression runner: replays a workload file through run_workload and diffs each step
+// that has an entry in a stored baseline; steps without one are not checked.
+// Usage: bench <workload.json> <baseline.json>
+
+use std::collections::HashMap;
+
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct BaselineEntry {
+    last_date: Option<String>,
+    rows_added: Option<i32>,
+    #[serde(default)]
+    avg_vectors: HashMap<String, Vec<Option<f64>>>,
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let workload_path = args.next().expect("usage: bench <workload.json> <baseline.json>");
+    let baseline_path = args.next().expect("usage: bench <workload.json> <baseline.json>");
+
+    let baseline: HashMap<String, BaselineEntry> = serde_json::from_str(
+        &std::fs::read_to_string(&baseline_path).expect("failed to read baseline file"),
+    )
+    .expect("failed to parse baseline file");
+
+    let app = tauri::Builder::default()
+        .plugin(tauri_plugin_shell::init())
+        .build(tauri::generate_context!())
+        .expect("failed to build headless app");
+
+    let report = tauri::async_runtime::block_on(four_seasons_lib::workload::run_workload(
+        app.handle().clone(),
+        workload_path,
+    ))
+    .expect("workload run failed");
+
+    let mut failures = Vec::new();
+    let mut checked = 0;
+    for (i, step) in report.steps.iter().enumerate() {
+        let key = format!("{}:{}:{}", i, step.tool, step.action);
+        let Some(expected) = baseline.get(&key) else {
+            continue;
+        };
+        checked += 1;
+
+        if !step.success {
+            failures.push(format!("{}: step failed: {:?}", key, step.error));
+            continue;
+        }
+        let Some(result) = &step.result else { continue };
+
+        if step.rows_added != expected.rows_added {
+            failures.push(format!(
+                "{}: rows_added mismatch (got {:?}, expected {:?})",
+                key, step.rows_added, expected.rows_added
+            ));
+        }
+        let last_date = result.get("last_date").and_then(|v| v.as_str());
+        if last_date != expected.last_date.as_deref() {
+            failures.push(format!(
+                "{}: last_date mismatch (got {:?}, expected {:?})",
+                key, last_date, expected.last_date
+            ));
+        }
+        for (field, expected_vec) in &expected.avg_vectors {
+            let actual: Option<Vec<Option<f64>>> =
+                result.get(field).and_then(|v| serde_json::from_value(v.clone()).ok());
+            if actual.as_ref() != Some(expected_vec) {
+                failures.push(format!("{}: {} mismatch", key, field));
+            }
+        }
+    }
+
+    if failures.is_empty() {
+        println!("{} of {} steps matched baseline", checked, report.steps.len());
+    } else {
+        for failure in &failures {
+            eprintln!("{}", failure);
+        }
+        std::process::exit(1);
+    }
+}
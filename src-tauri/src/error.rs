@@ -0,0 +1,55 @@
+use serde::Serialize;
+use thiserror::Error;
+
+// Error type returned by every #[command]. Serialized as a tagged JSON object
+// ({"type":"Io","message":"..."}) so the frontend can branch on what went wrong instead of
+// pattern-matching opaque strings. Every variant is a struct variant, even the single-string
+// ones, since internal tagging can't serialize a newtype payload that isn't itself a map.
+#[derive(Debug, Error, Serialize)]
+#[serde(tag = "type")]
+pub enum CommandError {
+    #[error("Failed to get app data dir: {message}")]
+    AppDir { message: String },
+
+    #[error("{message}")]
+    Io { message: String },
+
+    #[error("Failed to spawn sidecar: {message}")]
+    SidecarSpawn { message: String },
+
+    #[error("Sidecar exited with no output (stderr: {stderr})")]
+    SidecarStderr { stderr: String },
+
+    #[error("Sidecar '{bin_name}' timed out after {seconds}s")]
+    Timeout { bin_name: String, seconds: u64 },
+
+    #[error("Failed to parse JSON: {snippet} (output length: {len})")]
+    JsonParse { snippet: String, len: usize },
+
+    #[error("{message}")]
+    InvalidRequest { message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_every_variant() {
+        let variants = [
+            CommandError::AppDir { message: "x".into() },
+            CommandError::Io { message: "x".into() },
+            CommandError::SidecarSpawn { message: "x".into() },
+            CommandError::SidecarStderr { stderr: "x".into() },
+            CommandError::Timeout { bin_name: "seasonality".into(), seconds: 300 },
+            CommandError::JsonParse { snippet: "x".into(), len: 0 },
+            CommandError::InvalidRequest { message: "x".into() },
+        ];
+
+        for variant in variants {
+            let value = serde_json::to_value(&variant)
+                .unwrap_or_else(|e| panic!("{:?} failed to serialize: {}", variant, e));
+            assert!(value.get("type").is_some(), "{:?} missing tag field", variant);
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeasonalityResult {
+    pub success: bool,
+    pub message: Option<String>,
+    pub rows_added: Option<i32>,
+    pub last_date: Option<String>,
+    pub avg_2yr: Option<Vec<Option<f64>>>,
+    pub avg_5yr: Option<Vec<Option<f64>>>,
+    pub avg_6yr: Option<Vec<Option<f64>>>,
+    pub avg_10yr: Option<Vec<Option<f64>>>,
+    pub actual: Option<Vec<Option<f64>>>,
+    pub target_year: Option<i32>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct COTResult {
+    pub success: bool,
+    pub message: Option<String>,
+    pub rows_added: Option<i32>,
+    pub last_date: Option<String>,
+    pub dates: Option<Vec<String>>,
+    pub open_interest: Option<Vec<Option<f64>>>,
+    pub noncomm_net: Option<Vec<Option<f64>>>,
+    pub comm_net: Option<Vec<Option<f64>>>,
+    pub noncomm_long: Option<Vec<Option<f64>>>,
+    pub noncomm_short: Option<Vec<Option<f64>>>,
+    pub comm_long: Option<Vec<Option<f64>>>,
+    pub comm_short: Option<Vec<Option<f64>>>,
+    pub noncomm_net_change: Option<Vec<Option<f64>>>,
+    pub comm_net_change: Option<Vec<Option<f64>>>,
+    pub oi_change: Option<Vec<Option<f64>>>,
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use tauri::{command, AppHandle, Emitter, Manager};
+use tauri_plugin_shell::process::{CommandChild, CommandEvent};
+use tauri_plugin_shell::ShellExt;
+
+use crate::error::CommandError;
+use crate::models::{COTResult, SeasonalityResult};
+
+// Above this many bytes of raw sidecar output, dump a copy to a temp file for inspection.
+const DEBUG_DUMP_THRESHOLD: usize = 20_000;
+
+// Event the frontend subscribes to for incremental sidecar progress.
+const PROGRESS_EVENT: &str = "sidecar://progress";
+
+// Timeout the commands pass to run_sidecar when the caller doesn't give timeout_secs.
+pub const DEFAULT_SIDECAR_TIMEOUT: Duration = Duration::from_secs(300);
+
+// Source of internal JobRegistry keys for calls that don't pass their own job_id.
+static NEXT_INTERNAL_JOB_ID: AtomicU64 = AtomicU64::new(0);
+
+// Running sidecar child processes, keyed by job id, so a job can be cancelled or timed out
+// from outside the `run_sidecar` call that owns it. Managed state, registered in lib.rs and,
+// if not already present, by run_workload.
+#[derive(Default)]
+pub struct JobRegistry(Mutex<HashMap<String, CommandChild>>);
+
+// The shape invoke_tool deserializes a tool's sidecar output into before handing it back as
+// serde_json::Value, so a malformed sidecar response is caught here, not further down the line.
+pub enum ResultKind {
+    Seasonality,
+    Cot,
+}
+
+impl ResultKind {
+    fn to_value<T: serde::Serialize>(typed: T) -> Result<serde_json::Value, CommandError> {
+        serde_json::to_value(typed).map_err(|e| CommandError::JsonParse {
+            snippet: e.to_string(),
+            len: 0,
+        })
+    }
+
+    async fn run(
+        &self,
+        app: &AppHandle,
+        bin_name: &str,
+        args: Vec<String>,
+        job_id: Option<&str>,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, CommandError> {
+        match self {
+            ResultKind::Seasonality => {
+                let typed: SeasonalityResult =
+                    run_sidecar(app, bin_name, args, job_id, timeout).await?;
+                Self::to_value(typed)
+            }
+            ResultKind::Cot => {
+                let typed: COTResult = run_sidecar(app, bin_name, args, job_id, timeout).await?;
+                Self::to_value(typed)
+            }
+        }
+    }
+}
+
+// Describes one sidecar binary: its executable name, the (action, params) it understands, and
+// the typed result invoke_tool should deserialize its output into. params lists the flag names
+// (without the leading --) to look up on the incoming JSON object, in command-line order.
+pub struct SidecarTool {
+    pub bin_name: &'static str,
+    pub actions: &'static [(&'static str, &'static [&'static str])],
+    pub result_kind: ResultKind,
+}
+
+// The sidecar tools invoke_tool is allowed to run. Add an entry here to wire up a new binary
+// without writing a dedicated #[command].
+pub fn tool_registry() -> HashMap<&'static str, SidecarTool> {
+    let mut tools = HashMap::new();
+    tools.insert(
+        "seasonality",
+        SidecarTool {
+            bin_name: "seasonality",
+            actions: &[
+                ("fetch", &["symbol", "file"]),
+                ("calculate", &["file", "year"]),
+            ],
+            result_kind: ResultKind::Seasonality,
+        },
+    );
+    tools.insert(
+        "cot",
+        SidecarTool {
+            bin_name: "cot_data",
+            actions: &[
+                ("fetch", &["symbol", "file"]),
+                ("calculate", &["file", "years"]),
+            ],
+            result_kind: ResultKind::Cot,
+        },
+    );
+    tools
+}
+
+// Spawns bin_name with args, drains its stdout/stderr, logs stderr, and deserializes the final
+// result as T. Shared by every sidecar-backed command. The child is always tracked in the
+// JobRegistry (under job_id, or an internal id if none was given) so it can always be killed,
+// whether that's cancel_job or the timeout below firing.
+pub async fn run_sidecar<T: DeserializeOwned>(
+    app: &AppHandle,
+    bin_name: &str,
+    args: Vec<String>,
+    job_id: Option<&str>,
+    timeout: Duration,
+) -> Result<T, CommandError> {
+    let sidecar_command = app
+        .shell()
+        .sidecar(bin_name)
+        .map_err(|e| CommandError::SidecarSpawn {
+            message: format!("Failed to create sidecar command: {}", e),
+        })?
+        .args(args);
+
+    let (mut rx, child) = sidecar_command.spawn().map_err(|e| CommandError::SidecarSpawn {
+        message: e.to_string(),
+    })?;
+
+    let registry_key = job_id.map(str::to_string).unwrap_or_else(|| {
+        format!("__internal-{}", NEXT_INTERNAL_JOB_ID.fetch_add(1, Ordering::Relaxed))
+    });
+    app.state::<JobRegistry>()
+        .0
+        .lock()
+        .unwrap()
+        .insert(registry_key.clone(), child);
+
+    let drain = async {
+        let mut output = String::new();
+        let mut stderr_output = String::new();
+        while let Some(event) = rx.recv().await {
+            match event {
+                CommandEvent::Stdout(line) => {
+                    let line = String::from_utf8_lossy(&line).to_string();
+                    match serde_json::from_str::<serde_json::Value>(line.trim()) {
+                        Ok(message)
+                            if message.get("type").and_then(|t| t.as_str()) == Some("progress") =>
+                        {
+                            let _ = app.emit(PROGRESS_EVENT, message);
+                        }
+                        _ => output.push_str(&line),
+                    }
+                }
+                CommandEvent::Stderr(line) => {
+                    stderr_output.push_str(&String::from_utf8_lossy(&line));
+                }
+                _ => {}
+            }
+        }
+        (output, stderr_output)
+    };
+
+    let (output, stderr_output) = match tokio::time::timeout(timeout, drain).await {
+        Ok(result) => result,
+        Err(_) => {
+            if let Some(child) = app.state::<JobRegistry>().0.lock().unwrap().remove(&registry_key) {
+                let _ = child.kill();
+            }
+            return Err(CommandError::Timeout {
+                bin_name: bin_name.to_string(),
+                seconds: timeout.as_secs(),
+            });
+        }
+    };
+
+    app.state::<JobRegistry>().0.lock().unwrap().remove(&registry_key);
+
+    if !stderr_output.is_empty() {
+        eprintln!("Python stderr: {}", stderr_output);
+    }
+
+    if output.trim().is_empty() && !stderr_output.is_empty() {
+        return Err(CommandError::SidecarStderr {
+            stderr: stderr_output,
+        });
+    }
+
+    if output.len() > DEBUG_DUMP_THRESHOLD {
+        let dump_path = format!("/tmp/tauri_{}_output.txt", bin_name);
+        if let Err(e) = tokio::fs::write(&dump_path, &output).await {
+            eprintln!("Failed to write debug file: {}", e);
+        }
+    }
+
+    serde_json::from_str(output.trim()).map_err(|e| CommandError::JsonParse {
+        snippet: e.to_string(),
+        len: output.len(),
+    })
+}
+
+// Kills the sidecar child running under job_id, if any. Returns whether a job was found.
+#[command]
+pub fn cancel_job(app: AppHandle, job_id: String) -> Result<bool, CommandError> {
+    let registry = app.state::<JobRegistry>();
+    let mut jobs = registry.0.lock().unwrap();
+    match jobs.remove(&job_id) {
+        Some(child) => {
+            child.kill().map_err(|e| CommandError::Io {
+                message: format!("Failed to kill job '{}': {}", job_id, e),
+            })?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// Builds the sidecar argv for tool_id/action from params. Split out from invoke_tool so the
+// arg-building logic can be unit tested without a live AppHandle.
+fn build_args<'a>(
+    registry: &'a HashMap<&'static str, SidecarTool>,
+    tool_id: &str,
+    action: &str,
+    params: &serde_json::Value,
+) -> Result<(&'a SidecarTool, Vec<String>), CommandError> {
+    let tool = registry.get(tool_id).ok_or_else(|| CommandError::InvalidRequest {
+        message: format!("Unknown tool: {}", tool_id),
+    })?;
+
+    let param_names = tool
+        .actions
+        .iter()
+        .find(|(name, _)| *name == action)
+        .map(|(_, params)| *params)
+        .ok_or_else(|| CommandError::InvalidRequest {
+            message: format!("Unknown action '{}' for tool '{}'", action, tool_id),
+        })?;
+
+    let mut args = vec![action.to_string()];
+    for name in param_names {
+        let value = params.get(name).ok_or_else(|| CommandError::InvalidRequest {
+            message: format!("Missing parameter '{}' for {} {}", name, tool_id, action),
+        })?;
+        let value = match value {
+            serde_json::Value::String(s) => s.clone(),
+            other => other.to_string(),
+        };
+        args.push(format!("--{}", name));
+        args.push(value);
+    }
+
+    Ok((tool, args))
+}
+
+// Looks up tool_id/action in the registry, builds its argv, runs it, and deserializes the
+// output into the descriptor's result_kind. Lets a new tool be a registry entry instead of a
+// new #[command].
+#[command]
+pub async fn invoke_tool(
+    app: AppHandle,
+    tool_id: String,
+    action: String,
+    params: serde_json::Value,
+    job_id: Option<String>,
+    timeout_secs: Option<u64>,
+) -> Result<serde_json::Value, CommandError> {
+    let registry = tool_registry();
+    let (tool, args) = build_args(&registry, &tool_id, &action, &params)?;
+    let timeout = timeout_secs.map(Duration::from_secs).unwrap_or(DEFAULT_SIDECAR_TIMEOUT);
+
+    tool.result_kind
+        .run(&app, tool.bin_name, args, job_id.as_deref(), timeout)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_args_in_declared_param_order() {
+        let registry = tool_registry();
+        let params = serde_json::json!({ "symbol": "CL", "file": "/tmp/cl.csv" });
+
+        let (tool, args) = build_args(&registry, "seasonality", "fetch", &params).unwrap();
+
+        assert_eq!(tool.bin_name, "seasonality");
+        assert_eq!(
+            args,
+            vec!["fetch", "--symbol", "CL", "--file", "/tmp/cl.csv"]
+        );
+    }
+
+    #[test]
+    fn stringifies_non_string_params() {
+        let registry = tool_registry();
+        let params = serde_json::json!({ "file": "/tmp/cl.csv", "year": 2024 });
+
+        let (_, args) = build_args(&registry, "seasonality", "calculate", &params).unwrap();
+
+        assert_eq!(
+            args,
+            vec!["calculate", "--file", "/tmp/cl.csv", "--year", "2024"]
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_tool() {
+        let registry = tool_registry();
+        let err = build_args(&registry, "nope", "fetch", &serde_json::json!({})).unwrap_err();
+        assert!(matches!(err, CommandError::InvalidRequest { .. }));
+    }
+
+    #[test]
+    fn rejects_missing_param() {
+        let registry = tool_registry();
+        let params = serde_json::json!({ "symbol": "CL" });
+        let err = build_args(&registry, "seasonality", "fetch", &params).unwrap_err();
+        assert!(matches!(err, CommandError::InvalidRequest { .. }));
+    }
+}
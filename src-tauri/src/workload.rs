@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+use tauri::{command, AppHandle, Manager};
+
+use crate::error::CommandError;
+use crate::sidecar::{invoke_tool, JobRegistry};
+
+// One step of a workload file, e.g. {"tool":"seasonality","action":"fetch","symbol":"CL",...}.
+// Fields beyond tool/action are forwarded as-is to invoke_tool's params.
+#[derive(Debug, Deserialize)]
+struct WorkloadStep {
+    tool: String,
+    action: String,
+    #[serde(flatten)]
+    params: serde_json::Map<String, serde_json::Value>,
+}
+
+// Timing and outcome for a single executed WorkloadStep. result holds the sidecar response as
+// deserialized into the tool's result type (fields that type lacks are dropped) so callers (e.g. the bench binary) can diff it against a stored baseline.
+#[derive(Debug, Serialize)]
+pub struct StepReport {
+    pub tool: String,
+    pub action: String,
+    pub duration_ms: u128,
+    pub success: bool,
+    pub rows_added: Option<i32>,
+    pub payload_bytes: usize,
+    pub error: Option<String>,
+    pub result: Option<serde_json::Value>,
+}
+
+// Full report for a workload run, in step order.
+#[derive(Debug, Serialize)]
+pub struct WorkloadReport {
+    pub steps: Vec<StepReport>,
+}
+
+// Reads the JSON array of steps at path and runs each in sequence through invoke_tool, recording
+// timing, success, and payload size. Lets a fixed set of symbols be replayed as a benchmark.
+#[command]
+pub async fn run_workload(app: AppHandle, path: String) -> Result<WorkloadReport, CommandError> {
+    let contents = tokio::fs::read_to_string(&path).await.map_err(|e| CommandError::Io {
+        message: format!("Failed to read workload file '{}': {}", path, e),
+    })?;
+
+    let steps: Vec<WorkloadStep> = serde_json::from_str(&contents).map_err(|e| CommandError::JsonParse {
+        snippet: e.to_string(),
+        len: contents.len(),
+    })?;
+
+    // run_sidecar needs a JobRegistry; the headless bench builds its own app without one.
+    // manage() leaves an already registered JobRegistry unchanged.
+    app.manage(JobRegistry::default());
+
+    let mut reports = Vec::with_capacity(steps.len());
+    for step in steps {
+        reports.push(run_step(&app, step).await);
+    }
+
+    Ok(WorkloadReport { steps: reports })
+}
+
+async fn run_step(app: &AppHandle, step: WorkloadStep) -> StepReport {
+    let tool = step.tool;
+    let action = step.action;
+    let params = serde_json::Value::Object(step.params);
+
+    let started = std::time::Instant::now();
+    let result = invoke_tool(app.clone(), tool.clone(), action.clone(), params, None, None).await;
+    let duration_ms = started.elapsed().as_millis();
+
+    match result {
+        Ok(value) => StepReport {
+            tool,
+            action,
+            duration_ms,
+            success: true,
+            rows_added: value.get("rows_added").and_then(|v| v.as_i64()).map(|v| v as i32),
+            payload_bytes: serde_json::to_string(&value).map(|s| s.len()).unwrap_or(0),
+            error: None,
+            result: Some(value),
+        },
+        Err(e) => StepReport {
+            tool,
+            action,
+            duration_ms,
+            success: false,
+            rows_added: None,
+            payload_bytes: 0,
+            error: Some(e.to_string()),
+            result: None,
+        },
+    }
+}